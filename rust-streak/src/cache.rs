@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DEFAULT_CACHE_DIR: &str = ".cache";
+const DEFAULT_TTL_SECS: u64 = 300;
+
+#[derive(Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub body: String,
+    stored_at: u64,
+}
+
+impl CacheEntry {
+    pub fn fresh(body: String) -> Self {
+        CacheEntry {
+            body,
+            stored_at: now(),
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A small on-disk cache for GitHub API responses, keyed by request
+/// URL/query hash, so a frequently re-run job survives rate limits.
+/// Freshness is TTL-only: GitHub's GraphQL endpoint doesn't support
+/// conditional requests on POST, so there's no ETag/304 revalidation.
+pub struct Cache {
+    dir: PathBuf,
+    ttl_secs: u64,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf, ttl_secs: u64) -> Self {
+        let _ = std::fs::create_dir_all(&dir);
+        Cache { dir, ttl_secs }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    pub fn load(&self, key: &str) -> Option<CacheEntry> {
+        let bytes = std::fs::read(self.path_for(key)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    pub fn store(&self, key: &str, entry: &CacheEntry) {
+        if let Ok(bytes) = serde_json::to_vec(entry) {
+            let _ = std::fs::write(self.path_for(key), bytes);
+        }
+    }
+
+    pub fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        now().saturating_sub(entry.stored_at) < self.ttl_secs
+    }
+}
+
+fn parse_flag(flag: &str) -> Option<String> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(&format!("{flag}=")) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// Builds the cache from `--cache-dir`/`--cache-ttl` CLI flags, falling back
+/// to the `CACHE_DIR`/`CACHE_TTL_SECS` env vars and then sane defaults.
+pub fn get_cache() -> Cache {
+    let dir = parse_flag("--cache-dir")
+        .or_else(|| std::env::var("CACHE_DIR").ok())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR));
+
+    let ttl_secs = parse_flag("--cache-ttl")
+        .or_else(|| std::env::var("CACHE_TTL_SECS").ok())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_TTL_SECS);
+
+    Cache::new(dir, ttl_secs)
+}
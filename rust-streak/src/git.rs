@@ -0,0 +1,109 @@
+use crate::github::Window;
+use chrono::{NaiveDate, TimeZone, Utc};
+use gix::ObjectId;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Local repositories to read commit history from, as an alternative to the
+/// GitHub GraphQL calendar (e.g. for private or unpushed work).
+pub struct LocalSource {
+    pub repo_paths: Vec<PathBuf>,
+    pub author_email: Option<String>,
+    pub branches: Option<Vec<String>>,
+}
+
+fn collect_flag_values(flag: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            if let Some(value) = args.next() {
+                values.push(value);
+            }
+        } else if let Some(value) = arg.strip_prefix(&format!("{flag}=")) {
+            values.push(value.to_string());
+        }
+    }
+    values
+}
+
+/// Reads `--local-repo` (repeatable), `--author` and `--branch` (repeatable)
+/// CLI flags. Returns `None` when no `--local-repo` was given, so the
+/// GitHub backend stays the default.
+pub fn get_local_source() -> Option<LocalSource> {
+    let repo_paths: Vec<PathBuf> = collect_flag_values("--local-repo")
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    if repo_paths.is_empty() {
+        return None;
+    }
+
+    let author_email = collect_flag_values("--author").into_iter().next();
+    let branches = collect_flag_values("--branch");
+    let branches = if branches.is_empty() { None } else { Some(branches) };
+
+    Some(LocalSource {
+        repo_paths,
+        author_email,
+        branches,
+    })
+}
+
+fn resolve_tips(
+    repo: &gix::Repository,
+    branches: Option<&[String]>,
+) -> Result<Vec<ObjectId>, Box<dyn std::error::Error>> {
+    match branches {
+        Some(names) => names
+            .iter()
+            .map(|name| Ok(repo.find_reference(name)?.into_fully_peeled_id()?.detach()))
+            .collect(),
+        None => Ok(vec![repo.head_id()?.detach()]),
+    }
+}
+
+fn commit_date(seconds: i64) -> Option<NaiveDate> {
+    Utc.timestamp_opt(seconds, 0).single().map(|dt| dt.date_naive())
+}
+
+/// Walks commit history across one or more local repositories and populates
+/// the same `daily_contributions` map the GitHub GraphQL calendar builds, so
+/// downstream streak/active-day/heatmap logic is unaware of the source.
+pub fn get_local_contributions(
+    source: &LocalSource,
+    window: &Window,
+) -> Result<HashMap<NaiveDate, u32>, Box<dyn std::error::Error>> {
+    let mut daily_contributions: HashMap<NaiveDate, u32> = HashMap::new();
+
+    for repo_path in &source.repo_paths {
+        let repo = gix::open(repo_path)?;
+        let tips = resolve_tips(&repo, source.branches.as_deref())?;
+
+        // Walk all tips in one traversal so commits reachable from more than
+        // one branch (e.g. a feature branch sharing history with main) are
+        // deduplicated instead of counted once per tip.
+        for info in repo.rev_walk(tips).all()? {
+            let info = info?;
+            let commit = info.object()?;
+            let author = commit.author()?;
+
+            if let Some(expected) = &source.author_email {
+                if author.email.to_string() != *expected {
+                    continue;
+                }
+            }
+
+            let Some(date) = commit_date(author.time.seconds) else {
+                continue;
+            };
+            if date < window.since || date > window.until {
+                continue;
+            }
+
+            *daily_contributions.entry(date).or_insert(0) += 1;
+        }
+    }
+
+    Ok(daily_contributions)
+}
@@ -0,0 +1,347 @@
+use crate::cache::{Cache, CacheEntry};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<GraphQlError>>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+/// A paginated GraphQL connection: `nodes` mirrors the API's nullable-element
+/// list (a node can be null if it was deleted between the count and the
+/// fetch), alongside the `pageInfo` cursor needed to fetch further pages.
+#[derive(Deserialize)]
+struct GraphNodes<T> {
+    #[serde(rename = "totalCount")]
+    total_count: u32,
+    nodes: Vec<Option<T>>,
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+}
+
+#[derive(Deserialize)]
+struct PageInfo {
+    #[serde(rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Count {
+    #[serde(rename = "totalCount")]
+    total_count: u32,
+}
+
+#[derive(Deserialize)]
+struct RepoNode {
+    #[serde(rename = "stargazerCount")]
+    stargazer_count: u32,
+}
+
+#[derive(Deserialize)]
+struct ProfileQuery {
+    user: Option<ProfileUser>,
+}
+
+#[derive(Deserialize)]
+struct ProfileUser {
+    followers: Count,
+    following: Count,
+    repositories: GraphNodes<RepoNode>,
+    #[serde(rename = "contributionsCollection")]
+    contributions_collection: ContributionsCollection,
+}
+
+#[derive(Deserialize)]
+struct RepoPageQuery {
+    user: Option<RepoPageUser>,
+}
+
+#[derive(Deserialize)]
+struct RepoPageUser {
+    repositories: GraphNodes<RepoNode>,
+}
+
+#[derive(Deserialize)]
+struct ContributionsCollection {
+    #[serde(rename = "contributionCalendar")]
+    contribution_calendar: ContributionCalendar,
+}
+
+#[derive(Deserialize)]
+pub struct ContributionCalendar {
+    #[serde(rename = "totalContributions")]
+    pub total_contributions: u32,
+    pub weeks: Vec<ContributionWeek>,
+}
+
+#[derive(Deserialize)]
+pub struct ContributionWeek {
+    #[serde(rename = "contributionDays")]
+    pub contribution_days: Vec<ContributionDay>,
+}
+
+#[derive(Deserialize)]
+pub struct ContributionDay {
+    pub date: String,
+    #[serde(rename = "contributionCount")]
+    pub contribution_count: u32,
+}
+
+/// The profile, star and contribution data needed to render the widget,
+/// batched into a single paginated GraphQL round trip.
+pub struct Profile {
+    pub followers: u32,
+    pub following: u32,
+    pub public_repos: u32,
+    pub total_stars: u32,
+    pub calendar: ContributionCalendar,
+}
+
+pub fn get_github_token() -> Option<String> {
+    for key in ["GH_TOKEN", "GITHUB_TOKEN"] {
+        if let Ok(token) = std::env::var(key) {
+            let token = token.trim().to_string();
+            if !token.is_empty() {
+                return Some(token);
+            }
+        }
+    }
+    None
+}
+
+/// A reporting window, defaulting to the trailing year, overridable via
+/// `--since`/`--until` CLI flags or the `SINCE`/`UNTIL` env vars (`YYYY-MM-DD`).
+#[derive(Clone, Copy)]
+pub struct Window {
+    pub since: NaiveDate,
+    pub until: NaiveDate,
+}
+
+fn parse_window_arg(flag: &str, env_key: &str) -> Option<NaiveDate> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next().and_then(|v| NaiveDate::parse_from_str(&v, "%Y-%m-%d").ok());
+        }
+        if let Some(value) = arg.strip_prefix(&format!("{flag}=")) {
+            return NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+        }
+    }
+    std::env::var(env_key)
+        .ok()
+        .and_then(|v| NaiveDate::parse_from_str(&v, "%Y-%m-%d").ok())
+}
+
+pub fn get_window() -> Window {
+    let today = Utc::now().date_naive();
+    let since = parse_window_arg("--since", "SINCE").unwrap_or(today - Duration::days(365));
+    let until = parse_window_arg("--until", "UNTIL").unwrap_or(today);
+    Window { since, until }
+}
+
+fn parse_graphql_body<T: for<'de> Deserialize<'de>>(
+    body: &str,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let parsed: GraphQlResponse<T> = serde_json::from_str(body)?;
+    if let Some(errors) = parsed.errors {
+        let message = errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(format!("GitHub GraphQL response error: {}", message).into());
+    }
+
+    parsed
+        .data
+        .ok_or_else(|| "GitHub GraphQL response missing data".into())
+}
+
+async fn graphql<T: for<'de> Deserialize<'de>>(
+    client: &Client,
+    token: &str,
+    query: &str,
+    variables: Value,
+    cache: &Cache,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let cache_key = format!("{query}{variables}");
+    let cached = cache.load(&cache_key);
+
+    if let Some(entry) = &cached {
+        if cache.is_fresh(entry) {
+            return parse_graphql_body(&entry.body);
+        }
+    }
+
+    // GitHub's GraphQL endpoint doesn't emit ETags or honor conditional
+    // requests on POST /graphql (always a fresh 200), so there's no
+    // If-None-Match/304 dance here — freshness is TTL-only, via `cached`
+    // above and the `cache.store` below.
+    let req = client
+        .post("https://api.github.com/graphql")
+        .header("User-Agent", "rust-github-widget")
+        .header("Accept", "application/vnd.github+json")
+        .header("Authorization", format!("Bearer {}", token));
+
+    let resp = match req
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+    {
+        Ok(resp) => resp,
+        Err(err) => {
+            return match &cached {
+                Some(entry) => parse_graphql_body(&entry.body),
+                None => Err(err.into()),
+            };
+        }
+    };
+
+    let status = resp.status();
+
+    if !status.is_success() {
+        if let Some(entry) = cached {
+            return parse_graphql_body(&entry.body);
+        }
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("GitHub GraphQL error {}: {}", status, body).into());
+    }
+
+    let body = resp.text().await?;
+    let parsed = parse_graphql_body(&body);
+    // Only cache bodies that actually parsed: GitHub's GraphQL endpoint
+    // returns 200 with an `errors` payload for transient failures (secondary
+    // rate limits, unresolvable logins), and caching those would serve the
+    // error for the whole TTL instead of retrying.
+    if parsed.is_ok() {
+        cache.store(&cache_key, &CacheEntry::fresh(body));
+    }
+
+    parsed
+}
+
+fn stars_total<I: IntoIterator<Item = Option<RepoNode>>>(nodes: I) -> u32 {
+    nodes
+        .into_iter()
+        .flatten()
+        .map(|r| r.stargazer_count)
+        .sum()
+}
+
+/// Fetches profile, star and contribution data in one GraphQL round trip,
+/// paginating through `repositories` (beyond the first 100) so star totals
+/// aren't capped for prolific accounts.
+pub async fn get_profile(
+    client: &Client,
+    username: &str,
+    window: &Window,
+    cache: &Cache,
+) -> Result<Profile, Box<dyn std::error::Error>> {
+    let token = get_github_token()
+        .ok_or("GH_TOKEN or GITHUB_TOKEN is required for GitHub GraphQL API")?;
+
+    let from: DateTime<Utc> = window
+        .since
+        .and_hms_opt(0, 0, 0)
+        .ok_or("invalid --since date")?
+        .and_utc();
+    let to: DateTime<Utc> = window
+        .until
+        .and_hms_opt(23, 59, 59)
+        .ok_or("invalid --until date")?
+        .and_utc();
+
+    let query = r#"
+        query($login: String!, $from: DateTime!, $to: DateTime!) {
+          user(login: $login) {
+            followers { totalCount }
+            following { totalCount }
+            repositories(first: 100, ownerAffiliations: OWNER) {
+              totalCount
+              nodes { stargazerCount }
+              pageInfo { hasNextPage endCursor }
+            }
+            contributionsCollection(from: $from, to: $to) {
+              contributionCalendar {
+                totalContributions
+                weeks {
+                  contributionDays {
+                    date
+                    contributionCount
+                  }
+                }
+              }
+            }
+          }
+        }
+    "#;
+
+    let data: ProfileQuery = graphql(
+        client,
+        &token,
+        query,
+        json!({
+            "login": username,
+            "from": from.to_rfc3339(),
+            "to": to.to_rfc3339(),
+        }),
+        cache,
+    )
+    .await?;
+
+    let user = data.user.ok_or("GitHub GraphQL response missing user")?;
+    let mut total_stars = stars_total(user.repositories.nodes);
+    let mut page_info = user.repositories.page_info;
+
+    let page_query = r#"
+        query($login: String!, $cursor: String!) {
+          user(login: $login) {
+            repositories(first: 100, ownerAffiliations: OWNER, after: $cursor) {
+              totalCount
+              nodes { stargazerCount }
+              pageInfo { hasNextPage endCursor }
+            }
+          }
+        }
+    "#;
+
+    while page_info.has_next_page {
+        let cursor = page_info
+            .end_cursor
+            .ok_or("GitHub GraphQL pageInfo missing endCursor")?;
+
+        let page: RepoPageQuery = graphql(
+            client,
+            &token,
+            page_query,
+            json!({ "login": username, "cursor": cursor }),
+            cache,
+        )
+        .await?;
+
+        let repositories = page
+            .user
+            .ok_or("GitHub GraphQL response missing user")?
+            .repositories;
+        total_stars += stars_total(repositories.nodes);
+        page_info = repositories.page_info;
+    }
+
+    Ok(Profile {
+        followers: user.followers.total_count,
+        following: user.following.total_count,
+        public_repos: user.repositories.total_count,
+        total_stars,
+        calendar: user.contributions_collection.contribution_calendar,
+    })
+}
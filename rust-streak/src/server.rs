@@ -0,0 +1,48 @@
+use crate::cache::{self, Cache};
+use crate::github::get_window;
+use crate::theme::get_render_options;
+use crate::widget::render_widget;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use reqwest::Client;
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct AppState {
+    client: Client,
+    cache: Arc<Cache>,
+}
+
+/// Serves the widget on demand, with the username taken from the request
+/// path (e.g. `GET /swarajshaw.svg`).
+pub async fn serve(client: Client, port: u16) -> Result<(), Box<dyn std::error::Error>> {
+    let state = AppState {
+        client,
+        cache: Arc::new(cache::get_cache()),
+    };
+    let app = Router::new()
+        .route("/:username", get(widget_handler))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    println!("listening on http://0.0.0.0:{port}");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn widget_handler(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> impl IntoResponse {
+    let username = username.trim_end_matches(".svg");
+    let window = get_window();
+    let options = get_render_options();
+
+    match render_widget(&state.client, username, &window, &state.cache, &options).await {
+        Ok(svg) => ([(header::CONTENT_TYPE, "image/svg+xml")], svg).into_response(),
+        Err(err) => (StatusCode::BAD_GATEWAY, err.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,100 @@
+/// A named color scheme for the widget, selected via `--theme`/`--color-scheme`.
+#[derive(Clone, Copy)]
+pub enum Theme {
+    /// A GitHub-like green scheme (the default).
+    Green,
+    /// A warm red/amber scheme.
+    Amber,
+}
+
+/// Resolved hex colors for one mode (light or dark) of a [`Theme`].
+pub struct Palette {
+    pub accent_1: &'static str,
+    pub accent_2: &'static str,
+    pub accent_3: &'static str,
+    pub levels: [&'static str; 5],
+}
+
+impl Theme {
+    fn parse(name: &str) -> Option<Theme> {
+        match name.to_ascii_lowercase().as_str() {
+            "green" | "github" => Some(Theme::Green),
+            "amber" | "warm" | "red" => Some(Theme::Amber),
+            _ => None,
+        }
+    }
+
+    pub fn light(&self) -> Palette {
+        match self {
+            Theme::Green => Palette {
+                accent_1: "#0ea5e9",
+                accent_2: "#22c55e",
+                accent_3: "#f59e0b",
+                levels: ["#ebedf0", "#9be9a8", "#40c463", "#30a14e", "#216e39"],
+            },
+            Theme::Amber => Palette {
+                accent_1: "#f97316",
+                accent_2: "#ef4444",
+                accent_3: "#facc15",
+                levels: ["#fdf0e8", "#fed7aa", "#fb923c", "#ea580c", "#9a3412"],
+            },
+        }
+    }
+
+    pub fn dark(&self) -> Palette {
+        match self {
+            Theme::Green => Palette {
+                accent_1: "#38bdf8",
+                accent_2: "#4ade80",
+                accent_3: "#fbbf24",
+                levels: ["#161b22", "#0e4429", "#006d32", "#26a641", "#39d353"],
+            },
+            Theme::Amber => Palette {
+                accent_1: "#fb923c",
+                accent_2: "#f87171",
+                accent_3: "#fde047",
+                levels: ["#1f1410", "#4a2410", "#9a3412", "#ea580c", "#fb923c"],
+            },
+        }
+    }
+}
+
+/// The widget's visual options: color theme and whether weekend cells in
+/// the heatmap get a visual emphasis. Parsed from `--theme`/`--color-scheme`
+/// and `--weekend-emphasis` CLI flags.
+pub struct RenderOptions {
+    pub theme: Theme,
+    pub weekend_emphasis: bool,
+}
+
+fn parse_flag(flag: &str) -> Option<String> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(&format!("{flag}=")) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+fn has_flag(flag: &str) -> bool {
+    std::env::args().any(|arg| arg == flag)
+}
+
+pub fn get_render_options() -> RenderOptions {
+    let theme = parse_flag("--theme")
+        .or_else(|| parse_flag("--color-scheme"))
+        .or_else(|| std::env::var("WIDGET_THEME").ok())
+        .and_then(|name| Theme::parse(&name))
+        .unwrap_or(Theme::Green);
+
+    let weekend_emphasis = has_flag("--weekend-emphasis");
+
+    RenderOptions {
+        theme,
+        weekend_emphasis,
+    }
+}
@@ -0,0 +1,385 @@
+use crate::cache::Cache;
+use crate::github::{self, Window};
+use crate::theme::RenderOptions;
+use chrono::{Datelike, Duration, NaiveDate};
+use reqwest::Client;
+use std::collections::HashMap;
+
+/// The profile-level numbers shown in the widget footer. Zeroed out for
+/// data sources (like the local git backend) that don't have a GitHub
+/// profile to report.
+pub struct ProfileStats {
+    pub followers: u32,
+    pub following: u32,
+    pub public_repos: u32,
+    pub total_stars: u32,
+    pub total_contributions: u32,
+}
+
+/// Fetches a username's profile, repos and contribution calendar and renders
+/// the SVG widget. Shared by the file-writing CLI path and the HTTP server.
+pub async fn render_widget(
+    client: &Client,
+    username: &str,
+    window: &Window,
+    cache: &Cache,
+    options: &RenderOptions,
+) -> Result<String, Box<dyn std::error::Error>> {
+    // ---------- Profile, stars & contributions (single paginated GraphQL query) ----------
+    let profile = github::get_profile(client, username, window, cache).await?;
+
+    let mut daily_contributions: HashMap<NaiveDate, u32> = HashMap::new();
+    for week in &profile.calendar.weeks {
+        for day in &week.contribution_days {
+            let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")?;
+            daily_contributions.insert(date, day.contribution_count);
+        }
+    }
+
+    let stats = ProfileStats {
+        followers: profile.followers,
+        following: profile.following,
+        public_repos: profile.public_repos,
+        total_stars: profile.total_stars,
+        total_contributions: profile.calendar.total_contributions,
+    };
+
+    Ok(render_svg(&stats, &daily_contributions, window, options))
+}
+
+/// Renders the widget from local git history instead of the GitHub API,
+/// for private or unpushed work. The GitHub and gix backends are
+/// interchangeable because this only depends on a `daily_contributions` map.
+pub fn render_local_widget(
+    daily_contributions: &HashMap<NaiveDate, u32>,
+    window: &Window,
+    options: &RenderOptions,
+) -> String {
+    let total_contributions: u32 = daily_contributions.values().sum();
+    let stats = ProfileStats {
+        followers: 0,
+        following: 0,
+        public_repos: 0,
+        total_stars: 0,
+        total_contributions,
+    };
+
+    render_svg(&stats, daily_contributions, window, options)
+}
+
+/// Number of trailing days, counting back from `window.until`, with at
+/// least one contribution.
+fn current_streak(daily_contributions: &HashMap<NaiveDate, u32>, window: &Window) -> i32 {
+    let mut streak = 0;
+    let mut d = window.until;
+    while d >= window.since && daily_contributions.get(&d).cloned().unwrap_or(0) > 0 {
+        streak += 1;
+        d -= Duration::days(1);
+    }
+    streak
+}
+
+/// Longest run of consecutive days with at least one contribution, anywhere
+/// in `daily_contributions`.
+fn longest_streak(daily_contributions: &HashMap<NaiveDate, u32>) -> i32 {
+    let mut longest = 0;
+    let mut streak = 0;
+    let mut days: Vec<_> = daily_contributions
+        .iter()
+        .filter_map(|(d, c)| if *c > 0 { Some(*d) } else { None })
+        .collect();
+    days.sort();
+
+    let mut prev = None;
+    for day in days {
+        if let Some(p) = prev {
+            if day == p + Duration::days(1) {
+                streak += 1;
+            } else {
+                streak = 1;
+            }
+        } else {
+            streak = 1;
+        }
+        longest = longest.max(streak);
+        prev = Some(day);
+    }
+    longest
+}
+
+/// Buckets a day's contribution count into one of 5 intensity levels
+/// (0 = none, 4 = busiest), scaled against the window's busiest day.
+fn heatmap_level(count: u32, max_count: u32) -> u32 {
+    if max_count == 0 || count == 0 {
+        0
+    } else {
+        ((count as f64 / max_count as f64 * 4.0).ceil() as u32).min(4)
+    }
+}
+
+fn render_svg(
+    stats: &ProfileStats,
+    daily_contributions: &HashMap<NaiveDate, u32>,
+    window: &Window,
+    options: &RenderOptions,
+) -> String {
+    let current_streak = current_streak(daily_contributions, window);
+    let longest = longest_streak(daily_contributions);
+
+    // ---------- Active days over the window ----------
+    let mut active_days = 0;
+    let mut commits_window = 0;
+
+    let mut day = window.since;
+    while day <= window.until {
+        let count = daily_contributions.get(&day).cloned().unwrap_or(0);
+        if count > 0 {
+            active_days += 1;
+            commits_window += count;
+        }
+        day += Duration::days(1);
+    }
+    let window_days = (window.until - window.since).num_days() + 1;
+
+    // ---------- Contribution heatmap (52-week grid) ----------
+    const CELL: i32 = 8;
+    const GAP: i32 = 1;
+    const STEP: i32 = CELL + GAP;
+
+    let max_count = daily_contributions.values().cloned().max().unwrap_or(0);
+
+    // Align columns to Sunday-started weeks, mirroring GitHub's calendar.
+    let start_weekday = window.since.weekday().num_days_from_sunday() as i64;
+    let grid_start = window.since - Duration::days(start_weekday);
+
+    let mut heatmap = String::new();
+    let mut cell_day = grid_start;
+    while cell_day <= window.until {
+        if cell_day >= window.since {
+            let offset = (cell_day - grid_start).num_days();
+            let weekday = (offset % 7) as i32;
+            let x = (offset / 7) as i32 * STEP;
+            let y = weekday * STEP;
+            let count = daily_contributions.get(&cell_day).cloned().unwrap_or(0);
+            let level = heatmap_level(count, max_count);
+            let is_weekend = weekday == 0 || weekday == 6;
+            let weekend_class = if options.weekend_emphasis && is_weekend {
+                " weekend"
+            } else {
+                ""
+            };
+            heatmap.push_str(&format!(
+                r#"<rect x="{x}" y="{y}" width="{CELL}" height="{CELL}" rx="2" class="lvl-{level}{weekend_class}"/>"#,
+            ));
+        }
+        cell_day += Duration::days(1);
+    }
+
+    let light = options.theme.light();
+    let dark = options.theme.dark();
+
+    // ---------- SVG ----------
+    // The five-level intensity palette lives in `:root`/`.lvl-*` custom
+    // properties (swapped per Theme below) rather than as gradient stops in
+    // `<defs>` — that's what lets light/dark mode and Theme selection share
+    // one set of `.lvl-0`..`.lvl-4` classes instead of duplicating markup.
+    // `<defs>` still only holds the background/accent gradients and blur.
+    format!(
+        r##"
+<svg width="560" height="240" viewBox="0 0 560 240" xmlns="http://www.w3.org/2000/svg">
+<style>
+:root {{
+  --bg-start: #f7f4ef;
+  --bg-end: #e0f2fe;
+  --card: rgba(255,255,255,0.92);
+  --text: #0f172a;
+  --muted: #64748b;
+  --border: rgba(15,23,42,0.08);
+  --weekend-stroke: rgba(15,23,42,0.35);
+  --accent-1: {light_accent_1};
+  --accent-2: {light_accent_2};
+  --accent-3: {light_accent_3};
+  --level-0: {light_level_0};
+  --level-1: {light_level_1};
+  --level-2: {light_level_2};
+  --level-3: {light_level_3};
+  --level-4: {light_level_4};
+}}
+@media (prefers-color-scheme: dark) {{
+  :root {{
+    --bg-start: #0b1220;
+    --bg-end: #0f172a;
+    --card: rgba(15,23,42,0.88);
+    --text: #e2e8f0;
+    --muted: #94a3b8;
+    --border: rgba(148,163,184,0.18);
+    --weekend-stroke: rgba(226,232,240,0.35);
+    --accent-1: {dark_accent_1};
+    --accent-2: {dark_accent_2};
+    --accent-3: {dark_accent_3};
+    --level-0: {dark_level_0};
+    --level-1: {dark_level_1};
+    --level-2: {dark_level_2};
+    --level-3: {dark_level_3};
+    --level-4: {dark_level_4};
+  }}
+}}
+
+text {{
+  font-family: "Space Grotesk", "Manrope", "Segoe UI", sans-serif;
+  fill: var(--text);
+}}
+.small {{ fill: var(--muted); font-size: 11px; letter-spacing: 0.02em; }}
+.label {{ fill: var(--muted); font-size: 10px; letter-spacing: 0.18em; }}
+.value {{ font-size: 24px; font-weight: 600; }}
+.title {{ font-size: 16px; font-weight: 600; letter-spacing: 0.06em; text-transform: uppercase; }}
+.chip {{ fill: var(--text); font-size: 10px; letter-spacing: 0.14em; }}
+.bar {{ fill: url(#barGrad); }}
+.lvl-0 {{ fill: var(--level-0); }}
+.lvl-1 {{ fill: var(--level-1); }}
+.lvl-2 {{ fill: var(--level-2); }}
+.lvl-3 {{ fill: var(--level-3); }}
+.lvl-4 {{ fill: var(--level-4); }}
+.weekend {{ stroke: var(--weekend-stroke); stroke-width: 1; }}
+</style>
+
+<defs>
+  <linearGradient id="bg" x1="0" y1="0" x2="1" y2="1">
+    <stop offset="0%" stop-color="var(--bg-start)"/>
+    <stop offset="100%" stop-color="var(--bg-end)"/>
+  </linearGradient>
+  <linearGradient id="barGrad" x1="0" y1="0" x2="0" y2="1">
+    <stop offset="0%" stop-color="var(--accent-1)"/>
+    <stop offset="100%" stop-color="var(--accent-2)"/>
+  </linearGradient>
+  <linearGradient id="spark" x1="0" y1="0" x2="1" y2="1">
+    <stop offset="0%" stop-color="var(--accent-1)" stop-opacity="0.9"/>
+    <stop offset="100%" stop-color="var(--accent-3)" stop-opacity="0.9"/>
+  </linearGradient>
+  <pattern id="grid" width="22" height="22" patternUnits="userSpaceOnUse">
+    <path d="M22 0H0V22" fill="none" stroke="rgba(15,23,42,0.06)" stroke-width="1"/>
+  </pattern>
+  <filter id="blur" x="-20%" y="-20%" width="140%" height="140%">
+    <feGaussianBlur stdDeviation="18"/>
+  </filter>
+</defs>
+
+<rect width="560" height="240" rx="28" fill="url(#bg)"/>
+<circle cx="72" cy="40" r="54" fill="url(#spark)" opacity="0.5" filter="url(#blur)"/>
+<circle cx="498" cy="196" r="64" fill="url(#spark)" opacity="0.35" filter="url(#blur)"/>
+
+<rect x="12" y="12" width="536" height="216" rx="22" fill="var(--card)" stroke="var(--border)"/>
+<rect x="12" y="12" width="536" height="216" rx="22" fill="url(#grid)" opacity="0.55"/>
+
+<text x="32" y="38" class="title">GitHub Activity 🥷</text>
+<rect x="426" y="22" width="106" height="20" rx="10" fill="url(#spark)" opacity="0.12"/>
+<text x="440" y="36" class="chip">ACTIVITY</text>
+
+<text x="32" y="84" class="value">🔥 {current_streak}</text>
+<text x="32" y="102" class="label">CURRENT STREAK</text>
+
+<text x="176" y="84" class="value">🏆 {longest}</text>
+<text x="176" y="102" class="label">LONGEST</text>
+
+<text x="304" y="84" class="value">📈 {active_days}/{window_days}</text>
+<text x="304" y="102" class="label">ACTIVE DAYS</text>
+
+<rect x="24" y="120" width="512" height="78" rx="14" fill="rgba(15,23,42,0.04)"/>
+<g transform="translate(32,128)">{heatmap}</g>
+
+<text x="32" y="210" class="small">
+Repos {repos} · Stars {stars} · Followers {followers} · Following {following} · Commits({window_since} to {window_until}) {commits_window} · Total {total_contributions}
+</text>
+</svg>
+"##,
+        current_streak = current_streak,
+        longest = longest,
+        active_days = active_days,
+        window_days = window_days,
+        repos = stats.public_repos,
+        stars = stats.total_stars,
+        followers = stats.followers,
+        following = stats.following,
+        window_since = window.since,
+        window_until = window.until,
+        commits_window = commits_window,
+        total_contributions = stats.total_contributions,
+        heatmap = heatmap,
+        light_accent_1 = light.accent_1,
+        light_accent_2 = light.accent_2,
+        light_accent_3 = light.accent_3,
+        light_level_0 = light.levels[0],
+        light_level_1 = light.levels[1],
+        light_level_2 = light.levels[2],
+        light_level_3 = light.levels[3],
+        light_level_4 = light.levels[4],
+        dark_accent_1 = dark.accent_1,
+        dark_accent_2 = dark.accent_2,
+        dark_accent_3 = dark.accent_3,
+        dark_level_0 = dark.levels[0],
+        dark_level_1 = dark.levels[1],
+        dark_level_2 = dark.levels[2],
+        dark_level_3 = dark.levels[3],
+        dark_level_4 = dark.levels[4],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn heatmap_level_buckets_into_five_levels() {
+        assert_eq!(heatmap_level(0, 10), 0);
+        assert_eq!(heatmap_level(5, 0), 0);
+        assert_eq!(heatmap_level(1, 10), 1);
+        assert_eq!(heatmap_level(3, 10), 2);
+        assert_eq!(heatmap_level(8, 10), 4);
+        assert_eq!(heatmap_level(10, 10), 4);
+    }
+
+    #[test]
+    fn current_streak_counts_back_from_until() {
+        let mut daily = HashMap::new();
+        daily.insert(date(2026, 7, 24), 2);
+        daily.insert(date(2026, 7, 25), 1);
+        daily.insert(date(2026, 7, 26), 3);
+        let window = Window {
+            since: date(2026, 7, 1),
+            until: date(2026, 7, 26),
+        };
+        assert_eq!(current_streak(&daily, &window), 3);
+    }
+
+    #[test]
+    fn current_streak_stops_at_first_gap() {
+        let mut daily = HashMap::new();
+        daily.insert(date(2026, 7, 24), 1);
+        daily.insert(date(2026, 7, 26), 1);
+        let window = Window {
+            since: date(2026, 7, 1),
+            until: date(2026, 7, 26),
+        };
+        assert_eq!(current_streak(&daily, &window), 1);
+    }
+
+    #[test]
+    fn longest_streak_finds_best_run_anywhere_in_range() {
+        let mut daily = HashMap::new();
+        for day in 1..=3 {
+            daily.insert(date(2026, 7, day), 1);
+        }
+        daily.insert(date(2026, 7, 10), 1);
+        daily.insert(date(2026, 7, 11), 1);
+        assert_eq!(longest_streak(&daily), 3);
+    }
+
+    #[test]
+    fn longest_streak_is_zero_for_no_contributions() {
+        assert_eq!(longest_streak(&HashMap::new()), 0);
+    }
+}